@@ -2,9 +2,11 @@
 //! The state is then fed to other actors when server transitions to the leader mode.
 
 use crate::state_keeper::ZkSyncStateInitParams;
-use std::sync::mpsc;
-use std::thread;
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
 use zksync_circuit::witness::{
     ChangePubkeyOffChainWitness, CloseAccountWitness, DepositWitness, ForcedExitWitness,
     FullExitWitness, TransferToNewWitness, TransferWitness, WithdrawWitness, Witness,
@@ -13,6 +15,73 @@ use zksync_crypto::circuit::account::CircuitAccount;
 use zksync_crypto::circuit::CircuitAccountTree;
 use zksync_types::{BlockNumber, ZkSyncOp};
 
+/// Postgres `NOTIFY` channel the storage layer pushes to whenever a block becomes verified.
+const BLOCK_VERIFIED_CHANNEL: &str = "block_verified";
+
+/// How often `update()` writes a fresh checkpoint to disk.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// On-disk layout version for the checkpoint file. Bump this whenever `Checkpoint`'s shape
+/// changes, so a checkpoint written by an older binary is detected and discarded in favor of
+/// a full rebuild instead of being deserialized into garbage.
+const CHECKPOINT_FORMAT_VERSION: u32 = 1;
+
+fn checkpoint_path() -> PathBuf {
+    PathBuf::from("observer_mode_checkpoint.bin")
+}
+
+/// Reads and decodes the checkpoint at `path`, without checking it against storage. Returns
+/// `None` if the file can't be read (e.g. there's no file there), and `None` after logging
+/// why if it failed to deserialize (e.g. a truncated write) or it was written by an
+/// incompatible format version -- split out from
+/// `ObservedState::restore_from_checkpoint` so this part of the discard logic can be unit
+/// tested without a real storage connection.
+fn read_checkpoint(path: &std::path::Path) -> Option<Checkpoint> {
+    let bytes = fs::read(path).ok()?;
+    let checkpoint: Checkpoint = match bincode::deserialize(&bytes) {
+        Ok(checkpoint) => checkpoint,
+        Err(e) => {
+            log::warn!("observer mode checkpoint is corrupt, discarding: {}", e);
+            return None;
+        }
+    };
+    if checkpoint.format_version != CHECKPOINT_FORMAT_VERSION {
+        log::warn!(
+            "observer mode checkpoint has format version {} (expected {}), discarding",
+            checkpoint.format_version,
+            CHECKPOINT_FORMAT_VERSION
+        );
+        return None;
+    }
+    Some(checkpoint)
+}
+
+/// Whether `actual_root`, read from storage for the checkpoint's `circuit_tree_block`, matches
+/// the root the checkpoint itself recorded. `actual_root` is `None` when storage no longer has
+/// a record of that block at all (e.g. after a reorg), which is also treated as a mismatch.
+fn root_hash_matches(actual_root: Option<zksync_crypto::Fr>, checkpoint_root: zksync_crypto::Fr) -> bool {
+    actual_root == Some(checkpoint_root)
+}
+
+/// Serialized snapshot of [`ObservedState`], written periodically so a restart doesn't have
+/// to rebuild `circuit_acc_tree` from scratch by replaying every block from genesis.
+#[derive(Serialize)]
+struct CheckpointRef<'a> {
+    format_version: u32,
+    circuit_tree_block: BlockNumber,
+    circuit_acc_tree: &'a CircuitAccountTree,
+    state_keeper_init: &'a ZkSyncStateInitParams,
+}
+
+/// Owned counterpart of [`CheckpointRef`], used when reading a checkpoint back from disk.
+#[derive(Deserialize)]
+struct Checkpoint {
+    format_version: u32,
+    circuit_tree_block: BlockNumber,
+    circuit_acc_tree: CircuitAccountTree,
+    state_keeper_init: ZkSyncStateInitParams,
+}
+
 /// The state being observed during observer mode. Meant to be used later to initialize server actors.
 pub struct ObservedState {
     /// Used to initialize `ZkSyncStateKeeper`
@@ -23,6 +92,10 @@ pub struct ObservedState {
     pub circuit_tree_block: BlockNumber,
 
     pub connection_pool: zksync_storage::ConnectionPool,
+
+    /// Time of the last checkpoint write attempt (successful or not), or of construction
+    /// before the first one, used to throttle `write_checkpoint` to [`CHECKPOINT_INTERVAL`].
+    last_checkpoint_at: Instant,
 }
 
 impl ObservedState {
@@ -32,15 +105,32 @@ impl ObservedState {
             circuit_acc_tree: CircuitAccountTree::new(zksync_crypto::params::account_tree_depth()),
             circuit_tree_block: 0,
             connection_pool,
+            last_checkpoint_at: Instant::now(),
         }
     }
 
-    /// Init state by pulling verified and committed state from db.
+    /// Init state, preferring a warm restore from the on-disk checkpoint over rebuilding
+    /// `circuit_acc_tree` from scratch. Either way, blocks verified since are replayed
+    /// forward via `update_circuit_account_tree` so the result is always fully current.
     async fn init(&mut self) -> Result<(), anyhow::Error> {
-        self.init_circuit_tree().await?;
-        log::info!("updated circuit tree to block: {}", self.circuit_tree_block);
+        let restored = self.restore_from_checkpoint().await;
+        if restored {
+            log::info!(
+                "restored observed state from checkpoint at block: {}",
+                self.circuit_tree_block
+            );
+        } else {
+            self.init_circuit_tree().await?;
+            log::info!("rebuilt circuit tree from scratch up to block: {}", self.circuit_tree_block);
+        }
+        self.update_circuit_account_tree().await?;
+
         let mut storage = self.connection_pool.access_storage().await?;
-        self.state_keeper_init = ZkSyncStateInitParams::restore_from_db(&mut storage).await?;
+        if restored {
+            self.state_keeper_init.load_state_diff(&mut storage).await?;
+        } else {
+            self.state_keeper_init = ZkSyncStateInitParams::restore_from_db(&mut storage).await?;
+        }
         log::info!(
             "updated state keeper init params to block: {}",
             self.state_keeper_init.last_block_number
@@ -48,6 +138,93 @@ impl ObservedState {
         Ok(())
     }
 
+    /// Loads the newest on-disk checkpoint into `self`, if one exists and is valid. Returns
+    /// `false` (without modifying `self`) when there's no checkpoint, it was written by an
+    /// incompatible format version, it failed to deserialize (e.g. a truncated write), or its
+    /// tree root doesn't match what storage actually has at `circuit_tree_block` -- any of
+    /// which means the caller should fall back to a full rebuild instead. A checkpoint at
+    /// block 0 is not checked against storage.
+    ///
+    /// The root-hash check is what catches a checkpoint left over from a different network,
+    /// or one taken before a DB rewind/reorg: the format version alone says nothing about
+    /// which chain the checkpoint's state belongs to, so a version match alone isn't enough
+    /// to trust it.
+    async fn restore_from_checkpoint(&mut self) -> bool {
+        let checkpoint = match read_checkpoint(&checkpoint_path()) {
+            Some(checkpoint) => checkpoint,
+            None => return false,
+        };
+
+        if checkpoint.circuit_tree_block > 0 {
+            let expected_root_hash = match self.block_root_hash(checkpoint.circuit_tree_block).await {
+                Ok(hash) => hash,
+                Err(e) => {
+                    log::warn!(
+                        "failed to validate observer mode checkpoint against storage, discarding: {}",
+                        e
+                    );
+                    return false;
+                }
+            };
+            if !root_hash_matches(expected_root_hash, checkpoint.circuit_acc_tree.root_hash()) {
+                match expected_root_hash {
+                    Some(_) => log::warn!(
+                        "observer mode checkpoint's root hash at block {} doesn't match storage \
+                         (stale or wrong-network checkpoint?), discarding",
+                        checkpoint.circuit_tree_block
+                    ),
+                    None => log::warn!(
+                        "observer mode checkpoint references block {} which storage no longer has \
+                         (reorg?), discarding",
+                        checkpoint.circuit_tree_block
+                    ),
+                }
+                return false;
+            }
+        }
+
+        self.circuit_acc_tree = checkpoint.circuit_acc_tree;
+        self.circuit_tree_block = checkpoint.circuit_tree_block;
+        self.state_keeper_init = checkpoint.state_keeper_init;
+        true
+    }
+
+    /// Returns the root hash storage has committed for `block_number`, or `None` if storage
+    /// no longer has a record of that block (e.g. after a reorg).
+    async fn block_root_hash(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<zksync_crypto::Fr>, anyhow::Error> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(block_number)
+            .await
+            .map_err(|e| anyhow::format_err!("failed to load block {}: {}", block_number, e))?;
+        Ok(block.map(|b| b.new_root_hash))
+    }
+
+    /// Writes the current state to the on-disk checkpoint, via a temp file + rename so a
+    /// crash mid-write can never leave a half-written checkpoint for the next startup to
+    /// trip over.
+    fn write_checkpoint(&self) -> Result<(), anyhow::Error> {
+        let checkpoint = CheckpointRef {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            circuit_tree_block: self.circuit_tree_block,
+            circuit_acc_tree: &self.circuit_acc_tree,
+            state_keeper_init: &self.state_keeper_init,
+        };
+        let bytes = bincode::serialize(&checkpoint)?;
+
+        let path = checkpoint_path();
+        let tmp_path = path.with_extension("bin.tmp");
+        fs::write(&tmp_path, &bytes)?;
+        fs::rename(&tmp_path, &path)?;
+        log::info!("wrote observer mode checkpoint at block: {}", self.circuit_tree_block);
+        Ok(())
+    }
+
     async fn init_circuit_tree(&mut self) -> Result<(), anyhow::Error> {
         let mut storage = self.connection_pool.access_storage().await?;
 
@@ -83,6 +260,13 @@ impl ObservedState {
                 self.state_keeper_init.last_block_number
             );
         }
+
+        if self.last_checkpoint_at.elapsed() >= CHECKPOINT_INTERVAL {
+            if let Err(e) = self.write_checkpoint() {
+                log::warn!("failed to write observer mode checkpoint: {}", e);
+            }
+            self.last_checkpoint_at = Instant::now();
+        }
         Ok(())
     }
 
@@ -152,12 +336,18 @@ impl ObservedState {
 
 /// Accumulate state from db continuously and return that state on stop signal.
 ///
+/// Updates are triggered by a Postgres `LISTEN`/`NOTIFY` push on [`BLOCK_VERIFIED_CHANNEL`]
+/// rather than by polling on a fixed interval, so the observed circuit tree stays current
+/// within milliseconds of a block being verified. `heartbeat` is kept only as a fallback:
+/// if the notification is ever missed (or `LISTEN` couldn't be established), we still poll
+/// at that interval instead of going blind.
+///
 /// # Panics
 /// Panics on failed connection to db.
 pub async fn run(
     conn_pool: zksync_storage::ConnectionPool,
-    interval: Duration,
-    stop: mpsc::Receiver<()>,
+    heartbeat: Duration,
+    mut stop: oneshot::Receiver<()>,
 ) -> ObservedState {
     log::info!("starting observer mode");
     let mut observed_state = ObservedState::new(conn_pool);
@@ -165,22 +355,147 @@ pub async fn run(
         .init()
         .await
         .expect("failed to init observed state");
+
+    let mut notifications = match observed_state
+        .connection_pool
+        .listen_for_notifications(BLOCK_VERIFIED_CHANNEL)
+        .await
+    {
+        Ok(listener) => Some(listener),
+        Err(e) => {
+            log::warn!(
+                "failed to subscribe to `{}` notifications, falling back to heartbeat-only polling: {}",
+                BLOCK_VERIFIED_CHANNEL,
+                e
+            );
+            None
+        }
+    };
+
     loop {
-        let exit = match stop.try_recv() {
-            Err(mpsc::TryRecvError::Empty) => false,
-            Err(e) => {
-                panic!("stop channel recv error: {}", e);
+        tokio::select! {
+            _ = &mut stop => break,
+            _ = wait_for_notification_or_heartbeat(&mut notifications, heartbeat) => {
+                observed_state
+                    .update()
+                    .await
+                    .expect("failed to update observed state");
             }
-            Ok(_) => true,
-        };
-        thread::sleep(interval);
-        observed_state
-            .update()
-            .await
-            .expect("failed to update observed state");
-        if exit {
-            break;
         }
     }
     observed_state
 }
+
+/// Resolves as soon as a `block_verified` notification arrives, or after `heartbeat` has
+/// elapsed, whichever happens first.
+async fn wait_for_notification_or_heartbeat(
+    notifications: &mut Option<zksync_storage::NotificationListener>,
+    heartbeat: Duration,
+) {
+    match notifications {
+        Some(listener) => {
+            tokio::select! {
+                _ = listener.recv() => {}
+                _ = tokio::time::delay_for(heartbeat) => {}
+            }
+        }
+        None => tokio::time::delay_for(heartbeat).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+
+    fn empty_tree() -> CircuitAccountTree {
+        CircuitAccountTree::new(zksync_crypto::params::account_tree_depth())
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_bincode() {
+        let tree = empty_tree();
+        let state_keeper_init = ZkSyncStateInitParams::new();
+        let original_root_hash = tree.root_hash();
+
+        let checkpoint_ref = CheckpointRef {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            circuit_tree_block: 42,
+            circuit_acc_tree: &tree,
+            state_keeper_init: &state_keeper_init,
+        };
+        let bytes = bincode::serialize(&checkpoint_ref).expect("serialize checkpoint");
+        let decoded: Checkpoint = bincode::deserialize(&bytes).expect("deserialize checkpoint");
+
+        assert_eq!(decoded.format_version, CHECKPOINT_FORMAT_VERSION);
+        assert_eq!(decoded.circuit_tree_block, 42);
+        assert_eq!(decoded.circuit_acc_tree.root_hash(), original_root_hash);
+        assert_eq!(
+            decoded.state_keeper_init.last_block_number,
+            state_keeper_init.last_block_number
+        );
+    }
+
+    #[test]
+    fn discards_when_checkpoint_file_is_missing() {
+        let path = std::env::temp_dir().join(format!(
+            "observer_mode_test_missing_{}.bin",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        assert!(read_checkpoint(&path).is_none());
+    }
+
+    #[test]
+    fn discards_corrupt_checkpoint_bytes() {
+        let path = std::env::temp_dir().join(format!(
+            "observer_mode_test_corrupt_{}.bin",
+            std::process::id()
+        ));
+        fs::write(&path, b"not a valid bincode checkpoint").unwrap();
+
+        assert!(read_checkpoint(&path).is_none());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn discards_mismatched_format_version() {
+        let path = std::env::temp_dir().join(format!(
+            "observer_mode_test_version_{}.bin",
+            std::process::id()
+        ));
+        let tree = empty_tree();
+        let state_keeper_init = ZkSyncStateInitParams::new();
+        let checkpoint_ref = CheckpointRef {
+            format_version: CHECKPOINT_FORMAT_VERSION + 1,
+            circuit_tree_block: 0,
+            circuit_acc_tree: &tree,
+            state_keeper_init: &state_keeper_init,
+        };
+        fs::write(&path, &bincode::serialize(&checkpoint_ref).unwrap()).unwrap();
+
+        assert!(read_checkpoint(&path).is_none());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn root_hash_matches_when_storage_agrees() {
+        let root = empty_tree().root_hash();
+        assert!(root_hash_matches(Some(root), root));
+    }
+
+    #[test]
+    fn root_hash_mismatches_when_storage_disagrees() {
+        let root = empty_tree().root_hash();
+        let different_root = zksync_crypto::Fr::one();
+        assert_ne!(root, different_root, "test fixture needs two distinct roots");
+
+        assert!(!root_hash_matches(Some(different_root), root));
+    }
+
+    #[test]
+    fn root_hash_mismatches_on_reorg() {
+        // `None` means storage no longer has a record of the checkpointed block at all.
+        assert!(!root_hash_matches(None, empty_tree().root_hash()));
+    }
+}
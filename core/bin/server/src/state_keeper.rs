@@ -0,0 +1,56 @@
+//! In-memory parameters used to (re-)initialize `ZkSyncStateKeeper`.
+
+// External uses
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_types::BlockNumber;
+
+/// Parameters `ZkSyncStateKeeper` is initialized with, also checkpointed by observer mode so
+/// a warm restart doesn't have to re-derive them from scratch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkSyncStateInitParams {
+    /// Last block number these params are current as of.
+    pub last_block_number: BlockNumber,
+}
+
+impl ZkSyncStateInitParams {
+    pub fn new() -> Self {
+        Self {
+            last_block_number: 0,
+        }
+    }
+
+    /// Restores the params from scratch by reading the last committed block number from
+    /// `storage`.
+    pub async fn restore_from_db(
+        storage: &mut zksync_storage::StorageProcessor<'_>,
+    ) -> Result<Self, anyhow::Error> {
+        let last_block_number = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await
+            .map_err(|e| anyhow::format_err!("failed to load last committed block: {}", e))?;
+        Ok(Self { last_block_number })
+    }
+
+    /// Brings `self` up to date with any blocks committed since `last_block_number`.
+    pub async fn load_state_diff(
+        &mut self,
+        storage: &mut zksync_storage::StorageProcessor<'_>,
+    ) -> Result<(), anyhow::Error> {
+        self.last_block_number = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await
+            .map_err(|e| anyhow::format_err!("failed to load last committed block: {}", e))?;
+        Ok(())
+    }
+}
+
+impl Default for ZkSyncStateInitParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,9 @@
+//! Storage layer for zkSync: connection pooling, schemas, and migrations.
+//!
+//! `ConnectionPool`, `StorageProcessor`, and the various `*Schema` types used throughout the
+//! codebase (e.g. `storage.chain().block_schema()`) live elsewhere in this crate; this module
+//! only adds the `block_verified` notification subscription used by observer mode.
+
+mod notification_listener;
+
+pub use notification_listener::NotificationListener;
@@ -0,0 +1,35 @@
+//! Subscription to Postgres `LISTEN`/`NOTIFY` channels, used by observer mode to react to a
+//! newly verified block without polling for it.
+
+// External uses
+use sqlx::postgres::PgListener;
+// Workspace uses
+use crate::ConnectionPool;
+
+/// A live subscription to a single Postgres `NOTIFY` channel.
+pub struct NotificationListener {
+    inner: PgListener,
+}
+
+impl NotificationListener {
+    /// Waits for the next notification on the subscribed channel. The payload is ignored by
+    /// callers that only care *that* something changed (e.g. observer mode re-reads the
+    /// latest verified block from storage rather than trusting the notification payload).
+    pub async fn recv(&mut self) -> Result<(), anyhow::Error> {
+        self.inner.recv().await?;
+        Ok(())
+    }
+}
+
+impl ConnectionPool {
+    /// Subscribes to `channel`, returning a listener that resolves every time a `NOTIFY` is
+    /// issued on it (e.g. by the `block_verified_notify` trigger added alongside this).
+    pub async fn listen_for_notifications(
+        &self,
+        channel: &str,
+    ) -> Result<NotificationListener, anyhow::Error> {
+        let mut listener = PgListener::connect(&self.database_url()).await?;
+        listener.listen(channel).await?;
+        Ok(NotificationListener { inner: listener })
+    }
+}
@@ -0,0 +1,116 @@
+//! Background resend subsystem: re-broadcasts transactions the mempool appears to have
+//! dropped, modeled after the resend loop used by Solana's TPU client.
+
+// Built-in import
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+// External uses
+use tokio::sync::oneshot;
+// Workspace uses
+use zksync::Provider;
+// Local uses
+use crate::sent_transactions::{SentTransaction, SentTransactions};
+
+/// How often the resend task wakes up to look for stale transactions.
+pub const RESEND_INTERVAL: Duration = Duration::from_secs(2);
+/// How long a transaction may go unconfirmed before it becomes a resend candidate.
+pub const SEND_INTERVAL: Duration = Duration::from_secs(5);
+/// Maximum number of times a single transaction will be resent before we give up on it
+/// (the overall verify timeout will eventually fail the loadtest if it never lands).
+pub const MAX_RESEND_COUNT: u32 = 10;
+
+/// Periodically re-broadcasts any transaction in `sent_txs` that's still unconfirmed after
+/// [`SEND_INTERVAL`] and hasn't exceeded [`MAX_RESEND_COUNT`] retries. Transactions are
+/// removed from `sent_txs` by the caller as soon as they're seen in a committed block, so
+/// this task naturally stops resending them; it stops altogether once `stop` fires, which
+/// happens when every transaction has verified or the overall verify deadline passes.
+pub async fn run_resend_task(
+    sent_txs: Arc<Mutex<SentTransactions>>,
+    provider: Provider,
+    mut stop: oneshot::Receiver<()>,
+) {
+    loop {
+        let mut timeout = tokio::time::delay_for(RESEND_INTERVAL);
+        tokio::select! {
+            _ = &mut timeout => {}
+            // A `oneshot::Receiver` only resolves once the sender fires or is dropped (unlike
+            // `watch::Receiver::recv`, which resolves immediately with the current value on
+            // its very first call) so this arm doesn't win the race until `stop` is signalled
+            // or its sender goes away.
+            _ = &mut stop => break,
+        }
+
+        let to_resend: Vec<_> = {
+            let txs = sent_txs.lock().unwrap();
+            resend_candidates(&txs)
+        };
+
+        for tx in to_resend {
+            let (signed_tx, eth_sign) = tx.signed.clone().expect("checked by the filter above");
+            match provider.send_tx(signed_tx, eth_sign).await {
+                Ok(_) => {
+                    log::debug!(
+                        "Resent transaction {:?} (attempt {})",
+                        tx.tx_hash,
+                        tx.resend_count + 1
+                    );
+                    let mut txs = sent_txs.lock().unwrap();
+                    if let Some(entry) = txs.txs.get_mut(&tx.tx_hash) {
+                        entry.last_sent_at = Instant::now();
+                        entry.resend_count += 1;
+                    }
+                    txs.total_resend_count += 1;
+                }
+                Err(err) => {
+                    log::warn!("Failed to resend transaction {:?}: {}", tx.tx_hash, err);
+                }
+            }
+        }
+    }
+}
+
+/// Picks the transactions that are due for a resend: signed (so we're able to re-broadcast
+/// them at all), not yet at [`MAX_RESEND_COUNT`], and unconfirmed for longer than
+/// [`SEND_INTERVAL`]. Split out from `run_resend_task` so this selection logic is unit
+/// testable without spinning up the task or a `Provider`.
+fn resend_candidates(txs: &SentTransactions) -> Vec<SentTransaction> {
+    txs.txs
+        .values()
+        .filter(|tx| {
+            is_resend_candidate(tx.signed.is_some(), tx.resend_count, tx.last_sent_at.elapsed())
+        })
+        .cloned()
+        .collect()
+}
+
+/// The retry-bookkeeping decision at the heart of [`resend_candidates`], pulled out into a
+/// function of plain values so it's unit testable without needing a real `SentTransaction`
+/// (which in turn needs a real, signed `ZkSyncTx`).
+fn is_resend_candidate(has_signed_payload: bool, resend_count: u32, time_since_last_sent: Duration) -> bool {
+    has_signed_payload && resend_count < MAX_RESEND_COUNT && time_since_last_sent > SEND_INTERVAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_transactions_without_a_signed_payload() {
+        assert!(!is_resend_candidate(false, 0, SEND_INTERVAL * 2));
+    }
+
+    #[test]
+    fn skips_transactions_sent_recently() {
+        assert!(!is_resend_candidate(true, 0, SEND_INTERVAL / 2));
+    }
+
+    #[test]
+    fn skips_transactions_that_exhausted_their_resend_budget() {
+        assert!(!is_resend_candidate(true, MAX_RESEND_COUNT, SEND_INTERVAL * 2));
+    }
+
+    #[test]
+    fn picks_stale_signed_transactions_under_the_resend_budget() {
+        assert!(is_resend_candidate(true, MAX_RESEND_COUNT - 1, SEND_INTERVAL * 2));
+    }
+}
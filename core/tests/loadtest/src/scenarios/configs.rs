@@ -0,0 +1,47 @@
+// Built-in import
+use std::fs::File;
+use std::path::Path;
+// External uses
+use serde::{Deserialize, Serialize};
+
+/// Information about one of the accounts used to run the loadtest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub address: String,
+    pub private_key: String,
+}
+
+/// Configuration for the loadtest scenario, loaded from a JSON file supplied on the command line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadTestConfig {
+    /// Accounts to use for the loadtest.
+    pub input_accounts: Vec<AccountInfo>,
+
+    /// Amount of time to wait for all the transactions to be verified, in seconds.
+    pub verify_timeout_sec: u64,
+
+    pub deposit_initial_gwei: u64,
+    pub n_deposits: u32,
+    pub deposit_from_amount_gwei: u64,
+    pub deposit_to_amount_gwei: u64,
+
+    pub n_transfers: u32,
+    pub transfer_from_amount_gwei: u64,
+    pub transfer_to_amount_gwei: u64,
+
+    pub n_withdraws: u32,
+    pub withdraw_from_amount_gwei: u64,
+    pub withdraw_to_amount_gwei: u64,
+
+    /// Target offered load, in transactions per second, shared across all the per-account
+    /// senders. `0` (the default) disables pacing, sending as fast as the runtime allows.
+    #[serde(default)]
+    pub target_tps: u32,
+}
+
+impl LoadTestConfig {
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let file = File::open(path).expect("failed to open loadtest config");
+        serde_json::from_reader(file).expect("failed to parse loadtest config")
+    }
+}
@@ -0,0 +1,32 @@
+//! Loadtest scenarios: self-contained routines that exercise the node in a particular way.
+
+// Built-in import
+use std::path::PathBuf;
+use std::sync::Arc;
+// External uses
+use tokio::runtime::Runtime;
+// Local uses
+use crate::tps_counter::TPSCounter;
+
+pub mod configs;
+pub mod outgoing_tps;
+pub mod utils;
+
+/// Options common to every scenario, assembled by the loadtest binary before the chosen
+/// scenario is invoked.
+pub struct ScenarioContext {
+    /// Path to the scenario config file.
+    pub config_path: PathBuf,
+    /// Async runtime the scenario should drive itself with.
+    pub rt: Runtime,
+    /// Command-line options (e.g. the target node address).
+    pub options: CommonOptions,
+    /// Shared counter used to report the outgoing TPS.
+    pub tps_counter: Arc<TPSCounter>,
+}
+
+/// Command-line options shared by all the scenarios.
+#[derive(Debug, Clone)]
+pub struct CommonOptions {
+    pub net: zksync::Network,
+}
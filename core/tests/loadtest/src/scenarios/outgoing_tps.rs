@@ -7,7 +7,11 @@
 //! TPS as the transactions get accepted in the mempool.
 
 // Built-in import
-use std::{ops::Mul, sync::Arc, time::Duration};
+use std::{
+    ops::Mul,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 // External uses
 use num::BigUint;
 use tokio::runtime::Handle;
@@ -15,6 +19,8 @@ use tokio::runtime::Handle;
 use zksync::{Network, Provider};
 // Local uses
 use crate::{
+    rate_limiter::RateLimiter,
+    resender::run_resend_task,
     scenarios::{
         configs::LoadTestConfig,
         utils::{deposit_single, rand_amount, wait_for_verify},
@@ -25,6 +31,10 @@ use crate::{
     tps_counter::{run_tps_counter_printer, TPSCounter},
 };
 
+/// Burst cap for the `target_tps` pacer: how many tokens the bucket can hold above the
+/// steady refill rate, so a brief stall doesn't permanently throttle the catch-up.
+const PACER_BURST_CAP: u32 = 50;
+
 /// Runs the outgoing TPS scenario:
 /// sends the different types of transactions, and measures the TPS for the sending
 /// process (in other words, speed of the ZKSync node mempool).
@@ -56,13 +66,33 @@ pub fn run_scenario(mut ctx: ScenarioContext) {
         ctx.rt.handle().clone(),
         ctx.tps_counter,
     ));
+    let sent_txs = Arc::new(Mutex::new(sent_txs));
+
+    // Spawn the resend subsystem: it keeps re-broadcasting any transaction that's still
+    // unconfirmed after `SEND_INTERVAL`, so a dropped mempool entry doesn't hang the whole
+    // run until `verify_timeout_sec`. It stops once we signal it below.
+    let (stop_resend_sender, stop_resend_receiver) = tokio::sync::oneshot::channel();
+    let resend_handle = ctx.rt.spawn(run_resend_task(
+        Arc::clone(&sent_txs),
+        provider.clone(),
+        stop_resend_receiver,
+    ));
 
     // Wait until all the transactions are verified.
     log::info!("Waiting for all transactions to be verified");
-    ctx.rt
-        .block_on(wait_for_verify(sent_txs, verify_timeout_sec, &provider))
-        .expect("Verifying failed");
-    log::info!("Loadtest completed.");
+    let verify_result = ctx
+        .rt
+        .block_on(wait_for_verify(Arc::clone(&sent_txs), verify_timeout_sec, &provider));
+
+    stop_resend_sender.send(()).ok();
+    ctx.rt.block_on(resend_handle).expect("resend task panicked");
+
+    verify_result.expect("Verifying failed");
+
+    log::info!(
+        "Loadtest completed. Transactions resent: {}",
+        sent_txs.lock().unwrap().total_resend_count
+    );
 }
 
 // Sends the configured deposits, withdraws and transfers from each account concurrently.
@@ -75,6 +105,15 @@ async fn send_transactions(
 ) -> SentTransactions {
     // Send transactions from every account.
 
+    // A single pacer shared by every account sender holds the *combined* offered load to
+    // `target_tps`, rather than letting each account independently blast at that rate.
+    // `target_tps == 0` means no pacing: fall back to the previous burst-only behavior.
+    let pacer = if ctx.target_tps > 0 {
+        Some(Arc::new(RateLimiter::new(ctx.target_tps, PACER_BURST_CAP)))
+    } else {
+        None
+    };
+
     let join_handles = test_accounts
         .into_iter()
         .map(|account| {
@@ -83,6 +122,7 @@ async fn send_transactions(
                 ctx.clone(),
                 provider.clone(),
                 Arc::clone(&tps_counter),
+                pacer.clone(),
             ))
         })
         .collect::<Vec<_>>();
@@ -107,6 +147,7 @@ async fn send_transactions_from_acc(
     ctx: LoadTestConfig,
     provider: Provider,
     tps_counter: Arc<TPSCounter>,
+    pacer: Option<Arc<RateLimiter>>,
 ) -> Result<SentTransactions, anyhow::Error> {
     let mut sent_txs = SentTransactions::new();
     let addr_hex = hex::encode(test_wallet.address());
@@ -185,9 +226,18 @@ async fn send_transactions_from_acc(
     );
 
     for (tx, eth_sign) in tx_queue {
-        let tx_hash = provider.send_tx(tx, eth_sign).await?;
+        // Pace the offered load to `target_tps` rather than sending as fast as the runtime
+        // allows; a no-op when pacing is disabled.
+        if let Some(pacer) = &pacer {
+            pacer.acquire().await;
+        }
+
+        // Keep the signed payload around (not just the resulting hash): if the mempool
+        // drops this transaction, the resend subsystem needs to re-broadcast it without
+        // asking the wallet to sign it again.
+        let tx_hash = provider.send_tx(tx.clone(), eth_sign.clone()).await?;
         tps_counter.increment();
-        sent_txs.add_tx_hash(tx_hash);
+        sent_txs.add_tx(tx_hash, tx, eth_sign);
     }
 
     log::info!("Account: {}: all the transactions are sent", addr_hex);
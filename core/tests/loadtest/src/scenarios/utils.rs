@@ -0,0 +1,239 @@
+//! Helpers shared between the loadtest scenarios.
+
+// Built-in import
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+// External uses
+use num::BigUint;
+use rand::Rng;
+// Workspace uses
+use zksync::{Provider, MAX_TX_STATUSES_BATCH_SIZE};
+use zksync_types::tx::TxHash;
+// Local uses
+use crate::{sent_transactions::SentTransactions, test_accounts::TestWallet};
+
+/// Interval between polling attempts while waiting for transactions to commit/verify.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Performs a deposit of the requested amount and returns the priority operation id,
+/// which can later be used to query its status.
+pub async fn deposit_single(
+    test_wallet: &TestWallet,
+    deposit_amount: BigUint,
+    provider: &Provider,
+) -> Result<u64, anyhow::Error> {
+    let op_id = test_wallet.deposit(deposit_amount, provider).await?;
+    Ok(op_id)
+}
+
+/// Generates a pseudo-random amount in the `[from, to)` range (given in gwei).
+pub fn rand_amount(from: u64, to: u64) -> BigUint {
+    let amount = rand::thread_rng().gen_range(from, to);
+    BigUint::from(amount)
+}
+
+/// Waits for all the priority operations and transactions in `sent_txs` to become verified,
+/// failing fast if any of them is explicitly rejected.
+///
+/// Transaction statuses are polled in batches of up to [`MAX_TX_STATUSES_BATCH_SIZE`] hashes per
+/// RPC round trip: each tick we ask about everything still pending, drop whatever just got
+/// verified, and only keep polling the remainder. A hash the node doesn't recognize yet is
+/// treated as still-pending rather than as a failure, since it may simply not have propagated.
+///
+/// `sent_txs` is shared with the resend subsystem (see `crate::resender`): entries are removed
+/// from it as soon as they're seen in a committed block, so the resend task naturally stops
+/// re-broadcasting them.
+pub async fn wait_for_verify(
+    sent_txs: Arc<Mutex<SentTransactions>>,
+    timeout: Duration,
+    provider: &Provider,
+) -> Result<(), anyhow::Error> {
+    let start = Instant::now();
+
+    // NOTE: priority operations (deposits) are intentionally left out of the batching below
+    // and still polled one-by-one via `ethop_info`. There's no batched equivalent of
+    // `tx_statuses` for priority ops, and in practice there are few of them per account (one
+    // initial deposit plus `n_deposits`), so this remains an O(n) RPC path rather than the
+    // O(n / batch_size) path used for regular transactions just below.
+    let op_ids = sent_txs.lock().unwrap().op_ids.clone();
+    for op_id in op_ids {
+        wait_for_op_id_verify(op_id, deadline(start, timeout)?, provider).await?;
+    }
+
+    // Deduplicate hashes before batching: there's no point asking about the same hash twice
+    // in one round trip.
+    let mut pending: HashSet<TxHash> = sent_txs.lock().unwrap().txs.keys().copied().collect();
+
+    while !pending.is_empty() {
+        let remaining = deadline(start, timeout)?;
+
+        let hashes: Vec<TxHash> = pending.iter().copied().collect();
+        for batch in hashes.chunks(MAX_TX_STATUSES_BATCH_SIZE) {
+            let statuses = provider.tx_statuses(batch).await?;
+
+            // Anything already in a committed block is no longer a resend candidate.
+            {
+                let mut txs = sent_txs.lock().unwrap();
+                for (hash, status) in batch.iter().zip(&statuses) {
+                    if let Some(block) = status.as_ref().and_then(|info| info.block.as_ref()) {
+                        if block.committed {
+                            txs.txs.remove(hash);
+                        }
+                    }
+                }
+            }
+
+            let verified = apply_tx_statuses(&mut pending, batch.to_vec(), statuses)?;
+            for hash in verified {
+                sent_txs.lock().unwrap().txs.remove(&hash);
+            }
+        }
+
+        if pending.is_empty() {
+            break;
+        }
+
+        let sleep_for = std::cmp::min(POLL_INTERVAL, remaining);
+        tokio::time::delay_for(sleep_for).await;
+    }
+
+    Ok(())
+}
+
+/// Folds a batch of `tx_statuses` results into `pending`, removing any hash that's now
+/// verified and returning the hashes removed so the caller can drop them from `sent_txs` too.
+/// Bails out on the first explicit rejection. A hash the node doesn't recognize yet, or one
+/// that's committed but not yet verified, is left in `pending` for the next poll.
+///
+/// Split out from `wait_for_verify` so this batch-processing logic is unit testable without a
+/// real `Provider`.
+fn apply_tx_statuses(
+    pending: &mut HashSet<TxHash>,
+    batch: Vec<TxHash>,
+    statuses: Vec<Option<zksync::TransactionInfo>>,
+) -> Result<Vec<TxHash>, anyhow::Error> {
+    let mut verified = Vec::new();
+    for (hash, status) in batch.into_iter().zip(statuses) {
+        match status {
+            // The node doesn't know about this hash yet: keep waiting for it.
+            None => continue,
+            Some(info) if info.success == Some(false) => {
+                anyhow::bail!("Transaction {:?} was rejected: {:?}", hash, info.fail_reason);
+            }
+            Some(info) if info.block.map(|b| b.verified).unwrap_or(false) => {
+                pending.remove(&hash);
+                verified.push(hash);
+            }
+            Some(_) => {
+                // Committed but not verified yet, keep polling.
+            }
+        }
+    }
+    Ok(verified)
+}
+
+async fn wait_for_op_id_verify(
+    op_id: u64,
+    timeout: Duration,
+    provider: &Provider,
+) -> Result<(), anyhow::Error> {
+    let start = Instant::now();
+    loop {
+        let info = provider.ethop_info(op_id).await?;
+        if let Some(block) = info.block {
+            if block.verified {
+                return Ok(());
+            }
+        }
+        if start.elapsed() > timeout {
+            anyhow::bail!("Timeout elapsed while waiting for priority op {} to verify", op_id);
+        }
+        tokio::time::delay_for(POLL_INTERVAL).await;
+    }
+}
+
+/// Returns the time remaining until `timeout` has elapsed since `start`, or an error if it
+/// already has.
+fn deadline(start: Instant, timeout: Duration) -> Result<Duration, anyhow::Error> {
+    let elapsed = start.elapsed();
+    if elapsed > timeout {
+        anyhow::bail!("Timeout elapsed while waiting for transactions to verify");
+    }
+    Ok(timeout - elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync::{BlockInfo, TransactionInfo};
+
+    fn committed_block(verified: bool) -> BlockInfo {
+        BlockInfo {
+            block_number: 1,
+            committed: true,
+            verified,
+        }
+    }
+
+    #[test]
+    fn unknown_hash_stays_pending() {
+        let hash = TxHash::default();
+        let mut pending = [hash].iter().copied().collect::<HashSet<_>>();
+
+        let verified = apply_tx_statuses(&mut pending, vec![hash], vec![None]).unwrap();
+
+        assert!(verified.is_empty());
+        assert!(pending.contains(&hash));
+    }
+
+    #[test]
+    fn committed_but_unverified_stays_pending() {
+        let hash = TxHash::default();
+        let mut pending = [hash].iter().copied().collect::<HashSet<_>>();
+        let status = TransactionInfo {
+            executed: true,
+            success: Some(true),
+            fail_reason: None,
+            block: Some(committed_block(false)),
+        };
+
+        let verified = apply_tx_statuses(&mut pending, vec![hash], vec![Some(status)]).unwrap();
+
+        assert!(verified.is_empty());
+        assert!(pending.contains(&hash));
+    }
+
+    #[test]
+    fn verified_hash_is_removed_from_pending() {
+        let hash = TxHash::default();
+        let mut pending = [hash].iter().copied().collect::<HashSet<_>>();
+        let status = TransactionInfo {
+            executed: true,
+            success: Some(true),
+            fail_reason: None,
+            block: Some(committed_block(true)),
+        };
+
+        let verified = apply_tx_statuses(&mut pending, vec![hash], vec![Some(status)]).unwrap();
+
+        assert_eq!(verified, vec![hash]);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn rejected_transaction_bails() {
+        let hash = TxHash::default();
+        let mut pending = [hash].iter().copied().collect::<HashSet<_>>();
+        let status = TransactionInfo {
+            executed: true,
+            success: Some(false),
+            fail_reason: Some("nonce too low".to_string()),
+            block: None,
+        };
+
+        let result = apply_tx_statuses(&mut pending, vec![hash], vec![Some(status)]);
+
+        assert!(result.is_err());
+    }
+}
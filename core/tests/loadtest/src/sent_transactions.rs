@@ -0,0 +1,141 @@
+// Built-in import
+use std::collections::HashMap;
+use std::time::Instant;
+// External uses
+// Workspace uses
+use zksync_types::tx::{TxHash, ZkSyncTx};
+// Local uses
+
+/// A single transaction which has already been sent to the node and is
+/// awaiting commit/verification. Keeps the signed transaction around (rather
+/// than just its hash) so it can be resubmitted without being re-signed.
+#[derive(Debug, Clone)]
+pub struct SentTransaction {
+    pub tx_hash: TxHash,
+    /// Signed payload, kept around so the resend subsystem can re-broadcast
+    /// this transaction without asking the wallet to sign it again. Absent
+    /// for transactions whose sender doesn't support resubmission.
+    pub signed: Option<(ZkSyncTx, Option<zksync_types::tx::PackedEthSignature>)>,
+    /// Time of the most recent (re)broadcast of this transaction.
+    pub last_sent_at: Instant,
+    /// Number of times this transaction has been resent after the initial send.
+    pub resend_count: u32,
+}
+
+impl SentTransaction {
+    fn new(
+        tx_hash: TxHash,
+        signed: Option<(ZkSyncTx, Option<zksync_types::tx::PackedEthSignature>)>,
+    ) -> Self {
+        Self {
+            tx_hash,
+            signed,
+            last_sent_at: Instant::now(),
+            resend_count: 0,
+        }
+    }
+}
+
+/// Container for all the transactions sent by the loadtest, used to wait for
+/// their confirmation and, if necessary, resubmit the ones the mempool dropped.
+#[derive(Debug, Default)]
+pub struct SentTransactions {
+    /// Unique identifiers of the priority operations (e.g. deposits).
+    pub op_ids: Vec<u64>,
+    /// Sent `ZkSync` transactions, keyed by hash so a tx can be looked up and
+    /// updated (e.g. on resend) without a linear scan.
+    pub txs: HashMap<TxHash, SentTransaction>,
+    /// Total number of times any transaction in this batch had to be resent, reported
+    /// alongside the loadtest results so flakiness under load is observable.
+    pub total_resend_count: u32,
+}
+
+impl SentTransactions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an identifier of a sent priority operation (e.g. deposit).
+    pub fn add_op_id(&mut self, op_id: u64) {
+        self.op_ids.push(op_id);
+    }
+
+    /// Adds a hash of a sent transaction for which we don't keep the signed
+    /// data around (e.g. because it isn't a candidate for resubmission).
+    pub fn add_tx_hash(&mut self, tx_hash: TxHash) {
+        self.txs
+            .entry(tx_hash)
+            .or_insert_with(|| SentTransaction::new(tx_hash, None));
+    }
+
+    /// Adds a sent transaction together with its signed payload, so it can be
+    /// resubmitted later without needing to be re-signed.
+    pub fn add_tx(
+        &mut self,
+        tx_hash: TxHash,
+        tx: ZkSyncTx,
+        eth_sign: Option<zksync_types::tx::PackedEthSignature>,
+    ) {
+        self.txs
+            .insert(tx_hash, SentTransaction::new(tx_hash, Some((tx, eth_sign))));
+    }
+
+    /// Merges another batch of sent transactions into this one.
+    pub fn merge(&mut self, other: SentTransactions) {
+        self.op_ids.extend(other.op_ids);
+        self.txs.extend(other.txs);
+        self.total_resend_count += other.total_resend_count;
+    }
+
+    /// Returns `true` if there are no pending operations or transactions left.
+    pub fn is_empty(&self) -> bool {
+        self.op_ids.is_empty() && self.txs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_starts_empty() {
+        assert!(SentTransactions::new().is_empty());
+    }
+
+    #[test]
+    fn add_op_id_marks_non_empty() {
+        let mut sent = SentTransactions::new();
+        sent.add_op_id(7);
+        assert!(!sent.is_empty());
+        assert_eq!(sent.op_ids, vec![7]);
+    }
+
+    #[test]
+    fn add_tx_hash_dedupes_the_same_hash() {
+        let mut sent = SentTransactions::new();
+        let hash = TxHash::default();
+        sent.add_tx_hash(hash);
+        sent.add_tx_hash(hash);
+
+        assert_eq!(sent.txs.len(), 1);
+        assert!(sent.txs[&hash].signed.is_none());
+    }
+
+    #[test]
+    fn merge_combines_op_ids_txs_and_resend_counts() {
+        let mut a = SentTransactions::new();
+        a.add_op_id(1);
+        a.add_tx_hash(TxHash::default());
+        a.total_resend_count = 2;
+
+        let mut b = SentTransactions::new();
+        b.add_op_id(2);
+        b.total_resend_count = 3;
+
+        a.merge(b);
+
+        assert_eq!(a.op_ids, vec![1, 2]);
+        assert_eq!(a.txs.len(), 1);
+        assert_eq!(a.total_resend_count, 5);
+    }
+}
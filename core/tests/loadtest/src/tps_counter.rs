@@ -0,0 +1,49 @@
+// Built-in import
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Simple counter that measures the rate at which transactions are accepted
+/// so the loadtest can report the achieved TPS alongside the offered one.
+#[derive(Debug)]
+pub struct TPSCounter {
+    start: Instant,
+    count: AtomicU64,
+}
+
+impl Default for TPSCounter {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            count: AtomicU64::new(0),
+        }
+    }
+}
+
+impl TPSCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers one more sent transaction.
+    pub fn increment(&self) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the average TPS observed since the counter was created.
+    pub fn tps(&self) -> f64 {
+        let elapsed = self.start.elapsed().as_secs_f64();
+        if elapsed == 0.0 {
+            return 0.0;
+        }
+        self.count.load(Ordering::Relaxed) as f64 / elapsed
+    }
+}
+
+/// Periodically prints the current TPS to the log, until the holding task is dropped.
+pub async fn run_tps_counter_printer(tps_counter: Arc<TPSCounter>) {
+    loop {
+        tokio::time::delay_for(Duration::from_secs(1)).await;
+        log::info!("Current TPS: {:.2}", tps_counter.tps());
+    }
+}
@@ -0,0 +1,7 @@
+//! Rust SDK for interacting with a zkSync node.
+
+mod provider;
+
+pub use provider::{
+    BlockInfo, EthOpInfo, Network, Provider, TransactionInfo, MAX_TX_STATUSES_BATCH_SIZE,
+};
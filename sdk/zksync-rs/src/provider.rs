@@ -0,0 +1,134 @@
+//! JSON-RPC provider used to talk to a running node.
+
+// Built-in import
+// External uses
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+// Workspace uses
+use zksync_types::tx::{PackedEthSignature, TxHash, ZkSyncTx};
+
+/// Network the [`Provider`] should talk to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Rinkeby,
+    Ropsten,
+    Localhost,
+}
+
+impl Network {
+    fn default_rpc_addr(self) -> &'static str {
+        match self {
+            Network::Mainnet => "https://api.zksync.io/jsrpc",
+            Network::Rinkeby => "https://rinkeby-api.zksync.io/jsrpc",
+            Network::Ropsten => "https://ropsten-api.zksync.io/jsrpc",
+            Network::Localhost => "http://127.0.0.1:3030",
+        }
+    }
+}
+
+/// Block a transaction/priority operation is included in.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BlockInfo {
+    pub block_number: i64,
+    pub committed: bool,
+    pub verified: bool,
+}
+
+/// Status of a single sent transaction, as reported by the node.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TransactionInfo {
+    pub executed: bool,
+    pub success: Option<bool>,
+    pub fail_reason: Option<String>,
+    pub block: Option<BlockInfo>,
+}
+
+/// Status of a single sent priority operation (e.g. a deposit), as reported by the node.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EthOpInfo {
+    pub executed: bool,
+    pub block: Option<BlockInfo>,
+}
+
+/// Maximum number of hashes accepted by a single `tx_statuses` call. Mirrors the batching
+/// used by Solana's TPU client for signature-status polling.
+pub const MAX_TX_STATUSES_BATCH_SIZE: usize = 256;
+
+/// Thin JSON-RPC client for a zkSync node.
+#[derive(Debug, Clone)]
+pub struct Provider {
+    rpc_addr: String,
+    client: reqwest::Client,
+}
+
+impl Provider {
+    pub fn new(network: Network) -> Self {
+        Self {
+            rpc_addr: network.default_rpc_addr().to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn post<R: DeserializeOwned>(&self, method: &str, params: Value) -> Result<R, anyhow::Error> {
+        let request = json!({
+            "id": 1,
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .client
+            .post(&self.rpc_addr)
+            .json(&request)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("RPC error calling `{}`: {}", method, error);
+        }
+        let result = response
+            .get("result")
+            .ok_or_else(|| anyhow::format_err!("RPC response for `{}` has no result", method))?;
+        Ok(serde_json::from_value(result.clone())?)
+    }
+
+    /// Submits a transaction to the node's mempool, returning its hash.
+    pub async fn send_tx(
+        &self,
+        tx: ZkSyncTx,
+        eth_signature: Option<PackedEthSignature>,
+    ) -> Result<TxHash, anyhow::Error> {
+        self.post("tx_submit", json!([tx, eth_signature])).await
+    }
+
+    /// Returns the status of a single transaction.
+    pub async fn tx_info(&self, tx_hash: TxHash) -> Result<TransactionInfo, anyhow::Error> {
+        self.post("tx_info", json!([tx_hash])).await
+    }
+
+    /// Returns the status of a single priority operation (e.g. a deposit).
+    pub async fn ethop_info(&self, serial_id: u64) -> Result<EthOpInfo, anyhow::Error> {
+        self.post("ethop_info", json!([serial_id])).await
+    }
+
+    /// Returns the status of up to [`MAX_TX_STATUSES_BATCH_SIZE`] transactions in a single
+    /// RPC round trip. The result is aligned with `hashes`: an entry is `None` when the node
+    /// doesn't (yet) know about that hash, which callers should treat as still-pending rather
+    /// than failed.
+    pub async fn tx_statuses(
+        &self,
+        hashes: &[TxHash],
+    ) -> Result<Vec<Option<TransactionInfo>>, anyhow::Error> {
+        anyhow::ensure!(
+            hashes.len() <= MAX_TX_STATUSES_BATCH_SIZE,
+            "tx_statuses accepts at most {} hashes per call, got {}",
+            MAX_TX_STATUSES_BATCH_SIZE,
+            hashes.len()
+        );
+        self.post("tx_statuses_batch", json!([hashes])).await
+    }
+}